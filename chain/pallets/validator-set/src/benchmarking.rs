@@ -0,0 +1,35 @@
+//! Benchmarking for the validator-set pallet, covering `add_validator` and `remove_validator`
+//! across a range of existing validator-set sizes so their O(n) scan is priced accurately.
+
+use super::*;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use system::RawOrigin;
+
+const SEED: u32 = 0;
+
+fn bench_validators<T: Trait>(count: u32) -> Vec<T::AccountId> {
+    (0..count).map(|i| account("validator", i, SEED)).collect()
+}
+
+benchmarks! {
+    _ { }
+
+    add_validator {
+        let v in 1 .. 1000;
+        <Validators<T>>::put(bench_validators::<T>(v));
+
+        let new_validator: T::AccountId = whitelisted_caller();
+        T::Currency::make_free_balance_be(&new_validator, T::CandidacyBond::get() * 2u32.into());
+    }: _(RawOrigin::Root, new_validator)
+
+    remove_validator {
+        let v in (T::MinAuthorities::get() + 1) .. 1000;
+        let validators = bench_validators::<T>(v);
+        let to_remove = validators[0].clone();
+
+        T::Currency::make_free_balance_be(&to_remove, T::CandidacyBond::get() * 2u32.into());
+        T::Currency::reserve(&to_remove, T::CandidacyBond::get())?;
+        <ValidatorBond<T>>::insert(&to_remove, T::CandidacyBond::get());
+        <Validators<T>>::put(validators);
+    }: _(RawOrigin::Root, to_remove)
+}
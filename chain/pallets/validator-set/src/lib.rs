@@ -8,19 +8,86 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use frame_support::{decl_error, decl_event, decl_module, decl_storage, dispatch, StorageValue, traits::EstimateNextSessionRotation};
-use sp_runtime::traits::Convert;
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub use weights::WeightInfo;
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage, dispatch, ensure,
+    traits::{Currency, EnsureOrigin, EstimateNextSessionRotation, Get, ReservableCurrency},
+    StorageMap, StorageValue,
+};
+use sp_runtime::{
+    traits::{Convert, Zero},
+    Perbill,
+};
+use sp_staking::offence::{Kind, Offence, OffenceError, ReportOffence};
 use sp_std::prelude::*;
-use system::{self as system, ensure_root};
+use system::{self as system, ensure_root, ensure_signed};
+
+/// The balance type used by the candidacy bond, as determined by `Trait::Currency`.
+pub type BalanceOf<T> = <<T as Trait>::Currency as Currency<<T as system::Trait>::AccountId>>::Balance;
 
 pub trait Trait: system::Trait + session::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+
+    /// The number of offence reports a validator can accumulate before it is automatically
+    /// removed from the validator set.
+    type OffenceThreshold: Get<u32>;
+
+    /// Origin allowed to approve or reject a candidate from `PendingValidators`. Typically a
+    /// collective/council, but sudo/root satisfies it too.
+    type ApproveOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The minimum number of validators that must remain after a removal, so the network can't
+    /// be brought below BFT quorum and stall block production.
+    type MinAuthorities: Get<u32>;
+
+    /// Default number of blocks between session rotations, used for
+    /// `EstimateNextSessionRotation` until overridden by `set_session_period`.
+    type SessionPeriod: Get<Self::BlockNumber>;
+
+    /// Currency used to reserve the candidacy bond.
+    type Currency: ReservableCurrency<Self::AccountId>;
+
+    /// Amount reserved from a validator's account for as long as it's in the validator set.
+    type CandidacyBond: Get<BalanceOf<Self>>;
+
+    /// Weight information for this pallet's extrinsics.
+    type WeightInfo: WeightInfo;
 }
 
 decl_storage! {
     trait Store for Module<T: Trait> as ValidatorSet {
         pub Validators get(fn validators) config(): Option<Vec<T::AccountId>>;
         Flag get(fn flag): bool;
+
+        /// Number of offence reports accumulated per validator since its last session rotation.
+        OffenceCount get(fn offence_count): map hasher(blake2_128_concat) T::AccountId => u32;
+
+        /// Offence reports already processed, keyed by offending validator and the session in
+        /// which the offence occurred. Backs `is_known_offence` so a single equivocation
+        /// submitted redundantly by multiple reporters only counts toward `OffenceCount` once.
+        ReportedOffences get(fn reported_offences): map hasher(blake2_128_concat) (T::AccountId, SessionIndex) => bool;
+
+        /// Accounts that have asked to join the validator set and are awaiting approval.
+        pub PendingValidators get(fn pending_validators): Vec<T::AccountId>;
+
+        /// The block number at which the session was last rotated.
+        LastRotation get(fn last_rotation): T::BlockNumber;
+
+        /// Operator-configured override for `Trait::SessionPeriod`, set via
+        /// `set_session_period`. `None` means the trait's default still applies.
+        SessionPeriod get(fn session_period): Option<T::BlockNumber>;
+
+        /// Candidacy bond currently reserved for each validator, keyed by account.
+        ValidatorBond get(fn validator_bond): map hasher(blake2_128_concat) T::AccountId => BalanceOf<T>;
     }
 }
 
@@ -34,6 +101,21 @@ decl_event!(
 
         // Validator removed.
         ValidatorRemoved(AccountId),
+
+        // Validator automatically removed after crossing the offence threshold.
+        ValidatorRemovedForOffence(AccountId),
+
+        // An account asked to join the validator set and is awaiting council approval.
+        ValidatorRequested(AccountId),
+
+        // A pending candidate was approved and added to the validator set.
+        ValidatorApproved(AccountId),
+
+        // A pending candidate was rejected.
+        ValidatorRejected(AccountId),
+
+        // A validator voluntarily left the set and had its candidacy bond unreserved.
+        ValidatorWithdrawn(AccountId),
     }
 );
 
@@ -41,6 +123,16 @@ decl_error! {
     /// Errors for the module.
     pub enum Error for Module<T: Trait> {
         NoValidators,
+        /// The account already has a pending request to join the validator set.
+        AlreadyPending,
+        /// The account is already part of the validator set.
+        AlreadyValidator,
+        /// The account has no pending request to join the validator set.
+        NotPending,
+        /// Removing this validator would bring the validator set below `Trait::MinAuthorities`.
+        TooLowValidatorCount,
+        /// The account is not currently a validator.
+        NotValidator,
     }
 }
 
@@ -51,46 +143,171 @@ decl_module! {
         /// Add a new validator using root/sudo privileges.
         ///
         /// New validator's session keys should be set in session module before calling this.
-        #[weight = frame_support::weights::SimpleDispatchInfo::default()]
+        #[weight = T::WeightInfo::add_validator(Self::validators().map(|v| v.len() as u32).unwrap_or(0))]
         pub fn add_validator(origin, validator_id: T::AccountId) -> dispatch::DispatchResult {
             ensure_root(origin)?;
-            let mut validators = Self::validators().ok_or(Error::<T>::NoValidators)?;
-            validators.push(validator_id.clone());
-            <Validators<T>>::put(validators);
-            // Calling rotate_session to queue the new session keys.
-            <session::Module<T>>::rotate_session();
+            Self::do_add_validator(validator_id.clone())?;
             Self::deposit_event(RawEvent::ValidatorAdded(validator_id));
-
-            // Triggering rotate session again for the queued keys to take effect.
-            Flag::put(true);
             Ok(())
         }
 
         /// Remove a validator using root/sudo privileges.
-        #[weight = frame_support::weights::SimpleDispatchInfo::default()]
+        #[weight = T::WeightInfo::remove_validator(Self::validators().map(|v| v.len() as u32).unwrap_or(0))]
         pub fn remove_validator(origin, validator_id: T::AccountId) -> dispatch::DispatchResult {
             ensure_root(origin)?;
-            let mut validators = Self::validators().ok_or(Error::<T>::NoValidators)?;
-            // Assuming that this will be a PoA network for enterprise use-cases,
-            // the validator count may not be too big; the for loop shouldn't be too heavy.
-            // In case the validator count is large, we need to find another way.
-            for (i, v) in validators.clone().into_iter().enumerate() {
-                if v == validator_id {
-                    validators.swap_remove(i);
-                }
-            }
-            <Validators<T>>::put(validators);
-            // Calling rotate_session to queue the new session keys.
-            <session::Module<T>>::rotate_session();
+            Self::do_remove_validator(validator_id.clone())?;
+            Self::release_bond(&validator_id);
             Self::deposit_event(RawEvent::ValidatorRemoved(validator_id));
+            Ok(())
+        }
 
-            // Triggering rotate session again for the queued keys to take effect.
-            Flag::put(true);
+        /// Ask to be considered for the validator set. Anyone can call this; admission is
+        /// subject to approval by `Trait::ApproveOrigin`.
+        ///
+        /// New validator's session keys should still be set in the session module before
+        /// calling this, so they're ready to go once approved.
+        #[weight = frame_support::weights::SimpleDispatchInfo::default()]
+        pub fn request_to_join(origin) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            let validators = Self::validators().unwrap_or_default();
+            ensure!(!validators.contains(&who), Error::<T>::AlreadyValidator);
+
+            let mut pending = Self::pending_validators();
+            ensure!(!pending.contains(&who), Error::<T>::AlreadyPending);
+            pending.push(who.clone());
+            <PendingValidators<T>>::put(pending);
+
+            Self::deposit_event(RawEvent::ValidatorRequested(who));
+            Ok(())
+        }
+
+        /// Approve a pending candidate and add it to the validator set. Gated by
+        /// `Trait::ApproveOrigin` so a council/collective, not only sudo, can admit members.
+        #[weight = frame_support::weights::SimpleDispatchInfo::default()]
+        pub fn approve_validator(origin, candidate: T::AccountId) -> dispatch::DispatchResult {
+            T::ApproveOrigin::ensure_origin(origin)?;
+            ensure!(Self::pending_validators().contains(&candidate), Error::<T>::NotPending);
+
+            // Only drop the join request once it has actually been honoured; `do_add_validator`
+            // is fallible (e.g. the candidacy bond reservation can fail) and there's no
+            // transactional rollback in this pallet, so reordering these would let a failed
+            // approval silently destroy the candidate's place in the queue.
+            Self::do_add_validator(candidate.clone())?;
+            Self::remove_pending(&candidate)?;
+            Self::deposit_event(RawEvent::ValidatorApproved(candidate));
+            Ok(())
+        }
+
+        /// Reject a pending candidate, dropping its join request.
+        #[weight = frame_support::weights::SimpleDispatchInfo::default()]
+        pub fn reject_validator(origin, candidate: T::AccountId) -> dispatch::DispatchResult {
+            T::ApproveOrigin::ensure_origin(origin)?;
+            Self::remove_pending(&candidate)?;
+
+            Self::deposit_event(RawEvent::ValidatorRejected(candidate));
+            Ok(())
+        }
+
+        /// Tune the session rotation cadence used by `EstimateNextSessionRotation`, overriding
+        /// `Trait::SessionPeriod` for the running chain.
+        #[weight = frame_support::weights::SimpleDispatchInfo::default()]
+        pub fn set_session_period(origin, period: T::BlockNumber) -> dispatch::DispatchResult {
+            ensure_root(origin)?;
+            <SessionPeriod<T>>::put(period);
+            Ok(())
+        }
+
+        /// Voluntarily leave the validator set and get the candidacy bond back.
+        #[weight = frame_support::weights::SimpleDispatchInfo::default()]
+        pub fn withdraw(origin) -> dispatch::DispatchResult {
+            let who = ensure_signed(origin)?;
+            let validators = Self::validators().ok_or(Error::<T>::NoValidators)?;
+            ensure!(validators.contains(&who), Error::<T>::NotValidator);
+
+            Self::do_remove_validator(who.clone())?;
+            Self::release_bond(&who);
+            Self::deposit_event(RawEvent::ValidatorWithdrawn(who));
             Ok(())
         }
     }
 }
 
+impl<T: Trait> Module<T> {
+    /// Drops `candidate` from `PendingValidators`, failing if it isn't there.
+    fn remove_pending(candidate: &T::AccountId) -> dispatch::DispatchResult {
+        let mut pending = Self::pending_validators();
+        let position = pending.iter().position(|v| v == candidate).ok_or(Error::<T>::NotPending)?;
+        pending.swap_remove(position);
+        <PendingValidators<T>>::put(pending);
+        Ok(())
+    }
+
+    /// Adds `validator_id` to the validator set and triggers the two-step session rotation.
+    /// Shared by the root-origin `add_validator` extrinsic and the council-approved onboarding
+    /// path; callers are responsible for depositing the appropriate event.
+    fn do_add_validator(validator_id: T::AccountId) -> dispatch::DispatchResult {
+        let mut validators = Self::validators().ok_or(Error::<T>::NoValidators)?;
+        ensure!(!validators.contains(&validator_id), Error::<T>::AlreadyValidator);
+
+        let bond = T::CandidacyBond::get();
+        T::Currency::reserve(&validator_id, bond)?;
+        <ValidatorBond<T>>::insert(&validator_id, bond);
+
+        validators.push(validator_id);
+        <Validators<T>>::put(validators);
+        // Calling rotate_session to queue the new session keys.
+        <session::Module<T>>::rotate_session();
+
+        // Triggering rotate session again for the queued keys to take effect.
+        Flag::put(true);
+        Ok(())
+    }
+
+    /// Unreserves `validator_id`'s candidacy bond, e.g. on voluntary exit or administrative
+    /// removal.
+    fn release_bond(validator_id: &T::AccountId) {
+        let bond = <ValidatorBond<T>>::take(validator_id);
+        if !bond.is_zero() {
+            T::Currency::unreserve(validator_id, bond);
+        }
+    }
+
+    /// Slashes (burns) `validator_id`'s candidacy bond, used when removal is offence-driven.
+    fn slash_bond(validator_id: &T::AccountId) {
+        let bond = <ValidatorBond<T>>::take(validator_id);
+        if !bond.is_zero() {
+            let (_slashed, _remainder) = T::Currency::slash_reserved(validator_id, bond);
+        }
+    }
+
+    /// Swap-removes `validator_id` from the validator set and triggers the two-step session
+    /// rotation. Shared by the root-origin `remove_validator` extrinsic and the offence-based
+    /// automatic removal path; callers are responsible for depositing the appropriate event.
+    fn do_remove_validator(validator_id: T::AccountId) -> dispatch::DispatchResult {
+        let mut validators = Self::validators().ok_or(Error::<T>::NoValidators)?;
+        ensure!(
+            validators.len() as u32 > T::MinAuthorities::get(),
+            Error::<T>::TooLowValidatorCount
+        );
+        // Assuming that this will be a PoA network for enterprise use-cases,
+        // the validator count may not be too big; the for loop shouldn't be too heavy.
+        // In case the validator count is large, we need to find another way.
+        for (i, v) in validators.clone().into_iter().enumerate() {
+            if v == validator_id {
+                validators.swap_remove(i);
+            }
+        }
+        <Validators<T>>::put(validators);
+        // Calling rotate_session to queue the new session keys.
+        <session::Module<T>>::rotate_session();
+
+        // Triggering rotate session again for the queued keys to take effect.
+        Flag::put(true);
+        Ok(())
+    }
+}
+
 /// Indicates to the session module if the session should be rotated.
 /// We set this flag to true when we add/remove a validator.
 impl<T: Trait> session::ShouldEndSession<T::BlockNumber> for Module<T> {
@@ -103,8 +320,14 @@ impl<T: Trait> session::ShouldEndSession<T::BlockNumber> for Module<T> {
 /// same logical unit that provides [`ShouldEndSession`], yet, it gives a best effort estimate.
 impl<T: Trait> EstimateNextSessionRotation<T::BlockNumber> for Module<T> {
     fn estimate_next_session_rotation(now: T::BlockNumber) -> Option<T::BlockNumber> {
-        let now_block_number = <system::Module<T>>::block_number();
-        Some(now_block_number)
+        // An add/remove/approve call already queued an early rotation via `Flag`; the next
+        // session will begin as soon as that's processed, i.e. essentially now.
+        if Self::flag() {
+            return Some(now);
+        }
+
+        let period = Self::session_period().unwrap_or_else(T::SessionPeriod::get);
+        Some(Self::last_rotation() + period)
     }
 }
 
@@ -115,6 +338,12 @@ impl<T: Trait> session::SessionManager<T::AccountId> for Module<T> {
         // Flag is set to false so that the session doesn't keep rotating.
         Flag::put(false);
 
+        // A validator's offence count shouldn't carry across sessions; otherwise a handful of
+        // transient faults spread over the chain's lifetime would eventually evict a validator.
+        OffenceCount::<T>::remove_all();
+
+        <LastRotation<T>>::put(<system::Module<T>>::block_number());
+
         Self::validators()
     }
 
@@ -132,3 +361,112 @@ impl<T: Trait> Convert<T::AccountId, Option<T::AccountId>> for ValidatorOf<T> {
         Some(account)
     }
 }
+
+/// An offence that indicates a validator misbehaved, e.g. a GRANDPA equivocation or a repeated
+/// block-production failure.
+pub struct ValidatorOffence<Offender> {
+    /// The session index in which the offence occurred.
+    pub session_index: SessionIndex,
+    /// The size of the validator set at the time of the offence.
+    pub validator_set_count: u32,
+    /// The offending validator(s).
+    pub offenders: Vec<Offender>,
+    /// Fraction of the offender's stake (or, for a non-staked PoA validator, a nominal
+    /// weighting) that should be slashed. This pallet's `report_offence` does not apply it: an
+    /// offence-driven removal burns the whole candidacy bond.
+    pub slash_fraction: Perbill,
+}
+
+impl<Offender: Clone> Offence<Offender> for ValidatorOffence<Offender> {
+    const ID: Kind = *b"validator-set:ms";
+    type TimeSlot = SessionIndex;
+
+    fn offenders(&self) -> Vec<Offender> {
+        self.offenders.clone()
+    }
+
+    fn session_index(&self) -> SessionIndex {
+        self.session_index
+    }
+
+    fn validator_set_count(&self) -> u32 {
+        self.validator_set_count
+    }
+
+    fn time_slot(&self) -> Self::TimeSlot {
+        self.session_index
+    }
+
+    fn slash_fraction(&self, _offenders_count: u32) -> Perbill {
+        self.slash_fraction
+    }
+}
+
+/// Accepts offence reports against validators and, once a validator's accumulated offence
+/// weight crosses `Trait::OffenceThreshold`, removes it via the same path as `remove_validator`.
+impl<T: Trait> ReportOffence<T::AccountId, T::AccountId, ValidatorOffence<T::AccountId>> for Module<T> {
+    fn report_offence(
+        _reporters: Vec<T::AccountId>,
+        offence: ValidatorOffence<T::AccountId>,
+    ) -> Result<(), OffenceError> {
+        let time_slot = offence.time_slot();
+
+        for offender in offence.offenders() {
+            // The same proof is commonly submitted by several reporters; only the first one
+            // should move `OffenceCount`.
+            if ReportedOffences::<T>::contains_key((offender.clone(), time_slot)) {
+                continue;
+            }
+            ReportedOffences::<T>::insert((offender.clone(), time_slot), true);
+
+            let count = OffenceCount::<T>::mutate(&offender, |count| {
+                *count += 1;
+                *count
+            });
+
+            if count >= T::OffenceThreshold::get() {
+                if Self::do_remove_validator(offender.clone()).is_ok() {
+                    Self::slash_bond(&offender);
+                    Self::deposit_event(RawEvent::ValidatorRemovedForOffence(offender));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_known_offence(offenders: &[T::AccountId], time_slot: &SessionIndex) -> bool {
+        offenders
+            .iter()
+            .all(|offender| ReportedOffences::<T>::contains_key((offender.clone(), *time_slot)))
+    }
+}
+
+/// Implementation of Convert trait mapping a validator id to the identification used by the
+/// historical session machinery. As with `ValidatorOf`, we just return the same `AccountId`.
+pub struct IdentificationOf<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Trait> Convert<T::AccountId, Option<T::AccountId>> for IdentificationOf<T> {
+    fn convert(account: T::AccountId) -> Option<T::AccountId> {
+        Some(account)
+    }
+}
+
+impl<T: Trait> session::historical::ValidatorSet<T::AccountId> for Module<T> {
+    type ValidatorId = T::AccountId;
+    type ValidatorIdOf = ValidatorOf<T>;
+
+    fn session_index() -> SessionIndex {
+        <session::Module<T>>::current_index()
+    }
+
+    fn validators() -> Vec<Self::ValidatorId> {
+        Module::<T>::validators().unwrap_or_default()
+    }
+}
+
+/// Allows the offence pipeline to map a reported validator key back to its on-chain identity.
+impl<T: Trait> session::historical::ValidatorSetWithIdentification<T::AccountId> for Module<T> {
+    type Identification = T::AccountId;
+    type IdentificationOf = IdentificationOf<T>;
+}
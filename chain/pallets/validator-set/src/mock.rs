@@ -0,0 +1,119 @@
+//! Mock runtime used to exercise the validator-set pallet in `tests.rs`.
+
+use crate::{self as validator_set, Module, Trait, ValidatorOf};
+use frame_support::{impl_outer_origin, parameter_types, weights::Weight};
+use sp_core::H256;
+use sp_runtime::{
+    testing::{Header, UintAuthorityId},
+    traits::{BlakeTwo256, IdentityLookup},
+    Perbill,
+};
+
+impl_outer_origin! {
+    pub enum Origin for Test {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::one();
+}
+
+impl system::Trait for Test {
+    type Origin = Origin;
+    type Call = ();
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = u64;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = ();
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type ModuleToIndex = ();
+    type AccountData = balances::AccountData<u64>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+}
+
+parameter_types! {
+    pub const Period: u64 = 1;
+    pub const Offset: u64 = 0;
+    pub const DisabledValidatorsThreshold: Perbill = Perbill::from_percent(33);
+}
+
+impl session::Trait for Test {
+    type ValidatorId = <Self as system::Trait>::AccountId;
+    type ValidatorIdOf = ValidatorOf<Self>;
+    type ShouldEndSession = Module<Test>;
+    type NextSessionRotation = Module<Test>;
+    type SessionManager = Module<Test>;
+    type SessionHandler = ();
+    type Keys = UintAuthorityId;
+    type DisabledValidatorsThreshold = DisabledValidatorsThreshold;
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 1;
+}
+
+impl balances::Trait for Test {
+    type Balance = u64;
+    type Event = ();
+    type DustRemoval = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = system::Module<Test>;
+}
+
+frame_support::parameter_types! {
+    pub const OffenceThreshold: u32 = 3;
+    pub const MinAuthorities: u32 = 1;
+    pub const SessionPeriod: u64 = 10;
+    pub const CandidacyBond: u64 = 10;
+}
+
+impl Trait for Test {
+    type Event = ();
+    type OffenceThreshold = OffenceThreshold;
+    type ApproveOrigin = system::EnsureRoot<u64>;
+    type MinAuthorities = MinAuthorities;
+    type SessionPeriod = SessionPeriod;
+    type Currency = Balances;
+    type CandidacyBond = CandidacyBond;
+    type WeightInfo = ();
+}
+
+pub type Balances = balances::Module<Test>;
+pub type ValidatorSet = Module<Test>;
+
+/// Builds a test externality with a handful of accounts funded and validators 1, 2, 3 seeded.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+
+    balances::GenesisConfig::<Test> {
+        balances: vec![(1, 1_000), (2, 1_000), (3, 1_000), (4, 1_000)],
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    validator_set::GenesisConfig::<Test> {
+        validators: Some(vec![1, 2, 3]),
+    }
+    .assimilate_storage(&mut t)
+    .unwrap();
+
+    t.into()
+}
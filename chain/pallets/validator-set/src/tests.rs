@@ -0,0 +1,245 @@
+//! Behavioral tests for the validator-set pallet.
+
+use crate::{mock::*, Error, ValidatorOffence};
+use frame_support::{assert_noop, assert_ok, traits::EstimateNextSessionRotation};
+use sp_runtime::Perbill;
+use sp_staking::offence::ReportOffence;
+
+#[test]
+fn do_add_validator_reserves_the_candidacy_bond() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Balances::reserved_balance(&4), 0);
+
+        assert_ok!(ValidatorSet::do_add_validator(4));
+
+        assert_eq!(Balances::reserved_balance(&4), CandidacyBond::get());
+        assert_eq!(ValidatorSet::validator_bond(4), CandidacyBond::get());
+    });
+}
+
+#[test]
+fn do_add_validator_rejects_an_account_already_in_the_set() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(ValidatorSet::do_add_validator(1), Error::<Test>::AlreadyValidator);
+    });
+}
+
+#[test]
+fn release_bond_unreserves_and_clears_validator_bond() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::do_add_validator(4));
+
+        ValidatorSet::release_bond(&4);
+
+        assert_eq!(Balances::reserved_balance(&4), 0);
+        assert_eq!(ValidatorSet::validator_bond(4), 0);
+    });
+}
+
+#[test]
+fn slash_bond_burns_the_reserved_amount() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::do_add_validator(4));
+        let issuance_before = Balances::total_issuance();
+
+        ValidatorSet::slash_bond(&4);
+
+        assert_eq!(Balances::reserved_balance(&4), 0);
+        assert_eq!(ValidatorSet::validator_bond(4), 0);
+        assert_eq!(Balances::total_issuance(), issuance_before - CandidacyBond::get());
+    });
+}
+
+fn offence_against(who: u64, session_index: u32) -> ValidatorOffence<u64> {
+    ValidatorOffence {
+        session_index,
+        validator_set_count: 4,
+        offenders: vec![who],
+        slash_fraction: Perbill::from_percent(10),
+    }
+}
+
+fn report(who: u64, session_index: u32) -> Result<(), sp_staking::offence::OffenceError> {
+    <ValidatorSet as ReportOffence<u64, u64, ValidatorOffence<u64>>>::report_offence(
+        vec![],
+        offence_against(who, session_index),
+    )
+}
+
+fn is_known_offence(offenders: &[u64], time_slot: &u32) -> bool {
+    <ValidatorSet as ReportOffence<u64, u64, ValidatorOffence<u64>>>::is_known_offence(offenders, time_slot)
+}
+
+#[test]
+fn report_offence_removes_and_slashes_once_threshold_is_crossed() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::do_add_validator(4));
+        assert_eq!(Balances::reserved_balance(&4), CandidacyBond::get());
+
+        for session_index in 0..OffenceThreshold::get() - 1 {
+            assert_ok!(report(4, session_index));
+            assert!(ValidatorSet::validators().unwrap().contains(&4));
+        }
+
+        assert_ok!(report(4, OffenceThreshold::get() - 1));
+
+        assert!(!ValidatorSet::validators().unwrap().contains(&4));
+        assert_eq!(Balances::reserved_balance(&4), 0);
+        assert_eq!(ValidatorSet::validator_bond(4), 0);
+    });
+}
+
+#[test]
+fn is_known_offence_dedupes_reports_for_the_same_session() {
+    new_test_ext().execute_with(|| {
+        assert!(!is_known_offence(&[1], &0));
+
+        assert_ok!(report(1, 0));
+        assert_eq!(ValidatorSet::offence_count(1), 1);
+        assert!(is_known_offence(&[1], &0));
+
+        // The same proof submitted again by another reporter must not double count.
+        assert_ok!(report(1, 0));
+        assert_eq!(ValidatorSet::offence_count(1), 1);
+
+        // A distinct session's report is a genuinely new offence.
+        assert_ok!(report(1, 1));
+        assert_eq!(ValidatorSet::offence_count(1), 2);
+    });
+}
+
+#[test]
+fn request_to_join_adds_to_pending_validators() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::request_to_join(Origin::signed(4)));
+
+        assert!(ValidatorSet::pending_validators().contains(&4));
+    });
+}
+
+#[test]
+fn request_to_join_rejects_an_existing_validator() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(ValidatorSet::request_to_join(Origin::signed(1)), Error::<Test>::AlreadyValidator);
+    });
+}
+
+#[test]
+fn request_to_join_rejects_a_duplicate_request() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::request_to_join(Origin::signed(4)));
+
+        assert_noop!(ValidatorSet::request_to_join(Origin::signed(4)), Error::<Test>::AlreadyPending);
+    });
+}
+
+#[test]
+fn approve_validator_moves_a_pending_candidate_into_the_validator_set() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::request_to_join(Origin::signed(4)));
+
+        assert_ok!(ValidatorSet::approve_validator(Origin::root(), 4));
+
+        assert!(!ValidatorSet::pending_validators().contains(&4));
+        assert!(ValidatorSet::validators().unwrap().contains(&4));
+        assert_eq!(Balances::reserved_balance(&4), CandidacyBond::get());
+    });
+}
+
+#[test]
+fn approve_validator_rejects_a_candidate_that_never_asked_to_join() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(ValidatorSet::approve_validator(Origin::root(), 4), Error::<Test>::NotPending);
+
+        assert!(!ValidatorSet::validators().unwrap().contains(&4));
+    });
+}
+
+#[test]
+fn approve_validator_rejects_an_origin_other_than_approve_origin() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::request_to_join(Origin::signed(4)));
+
+        assert_noop!(
+            ValidatorSet::approve_validator(Origin::signed(2), 4),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn reject_validator_drops_the_pending_request_without_adding_it() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(ValidatorSet::request_to_join(Origin::signed(4)));
+
+        assert_ok!(ValidatorSet::reject_validator(Origin::root(), 4));
+
+        assert!(!ValidatorSet::pending_validators().contains(&4));
+        assert!(!ValidatorSet::validators().unwrap().contains(&4));
+    });
+}
+
+#[test]
+fn remove_validator_enforces_the_minimum_authority_count() {
+    new_test_ext().execute_with(|| {
+        // Genesis starts with validators [1, 2, 3] and `MinAuthorities` is 1: shrink the set
+        // down to the floor, then make sure one more removal is rejected.
+        assert_ok!(ValidatorSet::remove_validator(Origin::root(), 3));
+        assert_ok!(ValidatorSet::remove_validator(Origin::root(), 2));
+        assert_eq!(ValidatorSet::validators().unwrap(), vec![1]);
+
+        assert_noop!(
+            ValidatorSet::remove_validator(Origin::root(), 1),
+            Error::<Test>::TooLowValidatorCount
+        );
+        assert_eq!(ValidatorSet::validators().unwrap(), vec![1]);
+    });
+}
+
+#[test]
+fn estimate_next_session_rotation_returns_now_while_a_rotation_is_pending() {
+    new_test_ext().execute_with(|| {
+        // add_validator sets `Flag`, queuing an early rotation.
+        assert_ok!(ValidatorSet::add_validator(Origin::root(), 4));
+
+        assert_eq!(ValidatorSet::estimate_next_session_rotation(42), Some(42));
+    });
+}
+
+#[test]
+fn estimate_next_session_rotation_uses_last_rotation_plus_the_default_period_when_steady() {
+    new_test_ext().execute_with(|| {
+        <ValidatorSet as session::SessionManager<u64>>::new_session(1);
+        assert_eq!(ValidatorSet::flag(), false);
+
+        assert_eq!(
+            ValidatorSet::estimate_next_session_rotation(100),
+            Some(ValidatorSet::last_rotation() + SessionPeriod::get())
+        );
+    });
+}
+
+#[test]
+fn set_session_period_overrides_the_trait_default_for_estimation() {
+    new_test_ext().execute_with(|| {
+        <ValidatorSet as session::SessionManager<u64>>::new_session(1);
+
+        assert_ok!(ValidatorSet::set_session_period(Origin::root(), 99));
+
+        assert_eq!(
+            ValidatorSet::estimate_next_session_rotation(100),
+            Some(ValidatorSet::last_rotation() + 99)
+        );
+    });
+}
+
+#[test]
+fn weight_info_scales_with_the_validator_set_size() {
+    use crate::weights::{SubstrateWeight, WeightInfo};
+
+    // The placeholder weights in `weights.rs` are hand-written (see its doc comment) pending
+    // real `benchmark pallet` output, but they still need to reflect that `add_validator` and
+    // `remove_validator` scan the whole validator set, i.e. cost should grow with `v`.
+    assert!(SubstrateWeight::<Test>::add_validator(999) > SubstrateWeight::<Test>::add_validator(1));
+    assert!(SubstrateWeight::<Test>::remove_validator(999) > SubstrateWeight::<Test>::remove_validator(1));
+}